@@ -197,6 +197,75 @@ pub(crate) fn function_call<'a, N: Node + 'a>(
                         )
                     }
                 }
+                "matches" => {
+                    if a.len() == 2 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        Transform::Matches(Box::new(c), Box::new(b), None)
+                    } else if a.len() == 3 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        let d = a.pop().unwrap();
+                        Transform::Matches(Box::new(d), Box::new(c), Some(Box::new(b)))
+                    } else {
+                        // Wrong number of arguments
+                        Transform::Error(
+                            ErrorKind::ParseError,
+                            String::from("wrong number of arguments"),
+                        )
+                    }
+                }
+                "replace" => {
+                    if a.len() == 3 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        let d = a.pop().unwrap();
+                        Transform::Replace(Box::new(d), Box::new(c), Box::new(b), None)
+                    } else if a.len() == 4 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        let d = a.pop().unwrap();
+                        let e = a.pop().unwrap();
+                        Transform::Replace(Box::new(e), Box::new(d), Box::new(c), Some(Box::new(b)))
+                    } else {
+                        // Wrong number of arguments
+                        Transform::Error(
+                            ErrorKind::ParseError,
+                            String::from("wrong number of arguments"),
+                        )
+                    }
+                }
+                "tokenize" => {
+                    if a.len() == 1 {
+                        Transform::Tokenize(Box::new(a.pop().unwrap()), None, None)
+                    } else if a.len() == 2 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        Transform::Tokenize(Box::new(c), Some(Box::new(b)), None)
+                    } else if a.len() == 3 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        let d = a.pop().unwrap();
+                        Transform::Tokenize(Box::new(d), Some(Box::new(c)), Some(Box::new(b)))
+                    } else {
+                        // Wrong number of arguments
+                        Transform::Error(
+                            ErrorKind::ParseError,
+                            String::from("wrong number of arguments"),
+                        )
+                    }
+                }
+                "regex-group" => {
+                    if a.len() == 1 {
+                        Transform::RegexGroup(Box::new(a.pop().unwrap()))
+                    } else {
+                        // Wrong number of arguments
+                        Transform::Error(
+                            ErrorKind::ParseError,
+                            String::from("wrong number of arguments"),
+                        )
+                    }
+                }
                 "generate-id" => {
                     if a.is_empty() {
                         Transform::GenerateId(None)
@@ -62,7 +62,7 @@ pub(crate) fn none_of<N: Node>(
             let a = input.chars().next().unwrap();
             match s.find(|b| a == b) {
                 Some(_) => Err(ParseError::Combinator),
-                None => Ok(((&input[1..], state), a)),
+                None => Ok(((&input[a.len_utf8()..], state), a)),
             }
         }
     }
@@ -62,6 +62,9 @@ pub struct Context<N: Node> {
     // Grouping
     pub(crate) current_grouping_key: Option<Rc<Value>>,
     pub(crate) current_group: Sequence<N>,
+    // Regular expressions
+    // The captured substrings of the current match in xsl:analyze-string. The first is the whole match.
+    pub(crate) regex_groups: Vec<String>,
     // Keys
     // The declaration of a key. Keys are named, and each key can have multiple definitions.
     // Each definition is the pattern that matches nodes and the expression that computes the key value.
@@ -90,6 +93,7 @@ impl<N: Node> Context<N> {
             vars: HashMap::new(),
             current_grouping_key: None,
             current_group: Sequence::new(),
+            regex_groups: vec![],
             keys: HashMap::new(),
             key_values: HashMap::new(),
             od: OutputDefinition::new(),
@@ -420,6 +424,11 @@ impl<N: Node> Context<N> {
             Transform::SubstringAfter(s, t) => substring_after(self, stctxt, s, t),
             Transform::NormalizeSpace(s) => normalize_space(self, stctxt, s),
             Transform::Translate(s, m, t) => translate(self, stctxt, s, m, t),
+            Transform::Matches(s, p, f) => matches(self, stctxt, s, p, f),
+            Transform::Replace(s, p, r, f) => replace(self, stctxt, s, p, r, f),
+            Transform::Tokenize(s, p, f) => tokenize(self, stctxt, s, p, f),
+            Transform::AnalyzeString(s, r, f, m, n) => analyze_string(self, stctxt, s, r, f, m, n),
+            Transform::RegexGroup(g) => regex_group(self, stctxt, g),
             Transform::GenerateId(s) => generate_id(self, stctxt, s),
             Transform::Boolean(b) => boolean(self, stctxt, b),
             Transform::Not(b) => not(self, stctxt, b),
@@ -442,6 +451,7 @@ impl<N: Node> Context<N> {
             Transform::FormatTime(t, p, l, c, q) => format_time(self, stctxt, t, p, l, c, q),
             Transform::FormatNumber(v, p, d) => format_number(self, stctxt, v, p, d),
             Transform::FormatInteger(i, s) => format_integer(self, stctxt, i, s),
+            Transform::FormatNumbering(i, s, n) => format_numbering(self, stctxt, i, s, n),
             Transform::GenerateIntegers(start_at, select, n) => {
                 generate_integers(self, stctxt, start_at, select, n)
             }
@@ -477,6 +487,7 @@ impl<N: Node> From<Sequence<N>> for Context<N> {
             key_values: HashMap::new(),
             current_grouping_key: None,
             current_group: Sequence::new(),
+            regex_groups: vec![],
             od: OutputDefinition::new(),
             base_url: None,
             namespaces: vec![],
@@ -541,6 +552,10 @@ impl<N: Node> ContextBuilder<N> {
         self.0.current_grouping_key = Some(k);
         self
     }
+    pub fn regex_groups(mut self, g: Vec<String>) -> Self {
+        self.0.regex_groups = g;
+        self
+    }
     pub fn output_definition(mut self, od: OutputDefinition) -> Self {
         self.0.od = od;
         self
@@ -5,6 +5,7 @@ use std::rc::Rc;
 use url::Url;
 
 use crate::item::{Node, Sequence, SequenceTrait};
+use crate::pattern::Pattern;
 use crate::transform::context::{Context, ContextBuilder, StaticContext};
 use crate::transform::{do_sort, Grouping, Order, Transform};
 use crate::value::{Operator, Value};
@@ -290,42 +291,118 @@ fn group_adjacent<
     }
 }
 
-/// Evaluate a combinator for each group of items.
+/// Evaluate a combinator for each group of items. A new group is started by each selected item that matches any of the patterns. The first item always starts the first group.
 fn group_starting_with<
     N: Node,
     F: FnMut(&str) -> Result<(), Error>,
     G: FnMut(&str) -> Result<N, Error>,
     H: FnMut(&Url) -> Result<String, Error>,
 >(
-    _ctxt: &Context<N>,
-    _stctxt: &mut StaticContext<N, F, G, H>,
-    _pat: &Vec<Transform<N>>,
-    _s: &Transform<N>,
-    _body: &Transform<N>,
-    _o: &Vec<(Order, Transform<N>)>,
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    pat: &[Pattern<N>],
+    s: &Transform<N>,
+    body: &Transform<N>,
+    o: &[(Order, Transform<N>)],
 ) -> Result<Sequence<N>, Error> {
-    Err(Error::new(
-        ErrorKind::NotImplemented,
-        String::from("not implemented"),
-    ))
+    let mut groups: Vec<Sequence<N>> = Vec::new();
+    let mut curgrp: Sequence<N> = Vec::new();
+    for i in ctxt.dispatch(stctxt, s)? {
+        if !curgrp.is_empty() && pat.iter().any(|p| p.matches(ctxt, stctxt, &i)) {
+            // Close the previous group, start a new group with this item as its first member
+            groups.push(curgrp);
+            curgrp = vec![];
+        }
+        curgrp.push(i)
+    }
+    // Close the last group
+    if !curgrp.is_empty() {
+        groups.push(curgrp)
+    }
+    evaluate_pattern_groups(ctxt, stctxt, groups, body, o)
 }
 
-/// Evaluate a combinator for each group of items.
+/// Evaluate a combinator for each group of items. A group is ended by each selected item that matches any of the patterns. The next item, if any, starts a new group.
 pub fn group_ending_with<
     N: Node,
     F: FnMut(&str) -> Result<(), Error>,
     G: FnMut(&str) -> Result<N, Error>,
     H: FnMut(&Url) -> Result<String, Error>,
 >(
-    _ctxt: &Context<N>,
-    _stctxt: &mut StaticContext<N, F, G, H>,
-    _pat: &Vec<Transform<N>>,
-    _s: &Transform<N>,
-    _body: &Transform<N>,
-    _o: &Vec<(Order, Transform<N>)>,
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    pat: &[Pattern<N>],
+    s: &Transform<N>,
+    body: &Transform<N>,
+    o: &[(Order, Transform<N>)],
+) -> Result<Sequence<N>, Error> {
+    let mut groups: Vec<Sequence<N>> = Vec::new();
+    let mut curgrp: Sequence<N> = Vec::new();
+    for i in ctxt.dispatch(stctxt, s)? {
+        let last = pat.iter().any(|p| p.matches(ctxt, stctxt, &i));
+        curgrp.push(i);
+        if last {
+            // This item is the last member of the group
+            groups.push(curgrp);
+            curgrp = vec![];
+        }
+    }
+    // Close the last group
+    if !curgrp.is_empty() {
+        groups.push(curgrp)
+    }
+    evaluate_pattern_groups(ctxt, stctxt, groups, body, o)
+}
+
+/// Evaluate the body for each group formed by group-starting-with or group-ending-with.
+/// These groups do not have a grouping key.
+/// The initial item of each group becomes the context item.
+fn evaluate_pattern_groups<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    mut groups: Vec<Sequence<N>>,
+    body: &Transform<N>,
+    o: &[(Order, Transform<N>)],
 ) -> Result<Sequence<N>, Error> {
-    Err(Error::new(
-        ErrorKind::NotImplemented,
-        String::from("not implemented"),
-    ))
+    if !o.is_empty() {
+        // TODO: support multiple sort keys
+        // The sort key is evaluated with the initial item of the group as the context item
+        let mut keyed = groups
+            .into_iter()
+            .map(|v| {
+                ContextBuilder::from(ctxt)
+                    .context(vec![v[0].clone()])
+                    .current_group(v.clone())
+                    .build()
+                    .dispatch(stctxt, &o[0].1)
+                    // Assume string data type for now
+                    // TODO: support number data type
+                    // TODO: support all data types
+                    .map(|key_seq| (key_seq.to_string(), v))
+            })
+            .collect::<Result<Vec<(String, Sequence<N>)>, Error>>()?;
+        keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+        groups = keyed.into_iter().map(|(_, v)| v).collect();
+        if o[0].0 == Order::Descending {
+            groups.reverse();
+        }
+    }
+    // Now evaluate the body for each group
+    groups.iter().try_fold(vec![], |mut result, v| {
+        // Set current-group. There is no current-grouping-key.
+        let mut gctxt = ContextBuilder::from(ctxt)
+            .context(vec![v[0].clone()])
+            .previous_context(Some(v[0].clone()))
+            .current_group(v.clone())
+            .build();
+        gctxt.current_grouping_key = None;
+        let mut r = gctxt.dispatch(stctxt, body)?;
+        result.append(&mut r);
+        Ok(result)
+    })
 }
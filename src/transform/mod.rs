@@ -57,10 +57,11 @@ pub(crate) mod variables;
 #[allow(unused_imports)]
 use crate::item::Sequence;
 use crate::item::{Item, Node, NodeType, SequenceTrait};
+use crate::pattern::Pattern;
 use crate::qname::QualifiedName;
 use crate::transform::callable::ActualParameters;
 use crate::transform::context::{Context, ContextBuilder, StaticContext};
-use crate::transform::numbers::Numbering;
+use crate::transform::numbers::{NumberFormatting, Numbering};
 use crate::value::Operator;
 #[allow(unused_imports)]
 use crate::value::Value;
@@ -198,6 +199,40 @@ pub enum Transform<N: Node> {
     SubstringAfter(Box<Transform<N>>, Box<Transform<N>>),
     NormalizeSpace(Option<Box<Transform<N>>>),
     Translate(Box<Transform<N>>, Box<Transform<N>>, Box<Transform<N>>),
+    /// XPath matches function.
+    /// Arguments are the input string, the regular expression and the (optional) flags.
+    Matches(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
+    /// XPath replace function.
+    /// Arguments are the input string, the regular expression, the replacement string and the (optional) flags.
+    Replace(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
+    /// XPath tokenize function.
+    /// Arguments are the input string, the (optional) regular expression and the (optional) flags.
+    Tokenize(
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+        Option<Box<Transform<N>>>,
+    ),
+    /// XSLT xsl:analyze-string. See XSLT 17.1.
+    /// Arguments are the input string, the regular expression, the (optional) flags,
+    /// the body for matching substrings and the body for non-matching substrings.
+    AnalyzeString(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+    ),
+    /// XSLT regex-group function.
+    RegexGroup(Box<Transform<N>>),
     GenerateId(Option<Box<Transform<N>>>),
     Boolean(Box<Transform<N>>),
     Not(Box<Transform<N>>),
@@ -243,6 +278,16 @@ pub enum Transform<N: Node> {
     /// First argument is the integer to be formatted.
     /// Second argument is the format specification.
     FormatInteger(Box<Transform<N>>, Box<Transform<N>>),
+    /// Convert a sequence of place markers to a string for xsl:number.
+    /// See XSLT 12.4.
+    /// First argument is the sequence of integers to be formatted.
+    /// Second argument is the format specification.
+    /// Third argument is the remaining formatting attributes.
+    FormatNumbering(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Box<NumberFormatting<N>>,
+    ),
     /// Generate a sequence of integers. This is one half of the functionality of xsl:number.
     /// First argument is the start-at specification.
     /// Second argument is the select expression.
@@ -351,6 +396,13 @@ impl<N: Node> Debug for Transform<N> {
             Transform::SubstringAfter(s, t) => write!(f, "substring-after({:?}, {:?})", s, t),
             Transform::NormalizeSpace(_s) => write!(f, "normalize-space()"),
             Transform::Translate(s, t, u) => write!(f, "translate({:?}, {:?}, {:?})", s, t, u),
+            Transform::Matches(s, p, _) => write!(f, "matches({:?}, {:?})", s, p),
+            Transform::Replace(s, p, r, _) => write!(f, "replace({:?}, {:?}, {:?})", s, p, r),
+            Transform::Tokenize(s, _, _) => write!(f, "tokenize({:?})", s),
+            Transform::AnalyzeString(s, r, _, _, _) => {
+                write!(f, "analyze-string({:?}, {:?})", s, r)
+            }
+            Transform::RegexGroup(g) => write!(f, "regex-group({:?})", g),
             Transform::GenerateId(_) => write!(f, "generate-id()"),
             Transform::Boolean(b) => write!(f, "boolean({:?})", b),
             Transform::Not(b) => write!(f, "not({:?})", b),
@@ -371,6 +423,7 @@ impl<N: Node> Debug for Transform<N> {
             Transform::FormatTime(p, q, _, _, _) => write!(f, "format-time({:?}, {:?}, ...)", p, q),
             Transform::FormatNumber(v, p, _) => write!(f, "format-number({:?}, {:?})", v, p),
             Transform::FormatInteger(i, s) => write!(f, "format-integer({:?}, {:?})", i, s),
+            Transform::FormatNumbering(i, s, _) => write!(f, "format-numbering({:?}, {:?})", i, s),
             Transform::GenerateIntegers(_start_at, _select, _n) => write!(f, "generate-integers"),
             Transform::CurrentGroup => write!(f, "current-group"),
             Transform::CurrentGroupingKey => write!(f, "current-grouping-key"),
@@ -433,8 +486,8 @@ pub(crate) fn do_sort<
 #[derive(Clone, Debug)]
 pub enum Grouping<N: Node> {
     By(Vec<Transform<N>>),
-    StartingWith(Vec<Transform<N>>),
-    EndingWith(Vec<Transform<N>>),
+    StartingWith(Vec<Pattern<N>>),
+    EndingWith(Vec<Pattern<N>>),
     Adjacent(Vec<Transform<N>>),
 }
 
@@ -40,6 +40,31 @@ impl<N: Node> Numbering<N> {
     }
 }
 
+/// Specification for formatting numbers, other than the format picture. See XSLT 12.4.
+/// Each value is an attribute value template.
+#[derive(Clone, Debug, Default)]
+pub struct NumberFormatting<N: Node> {
+    grouping_separator: Option<Transform<N>>,
+    grouping_size: Option<Transform<N>>,
+    ordinal: Option<Transform<N>>,
+    lang: Option<Transform<N>>,
+}
+impl<N: Node> NumberFormatting<N> {
+    pub fn new(
+        grouping_separator: Option<Transform<N>>,
+        grouping_size: Option<Transform<N>>,
+        ordinal: Option<Transform<N>>,
+        lang: Option<Transform<N>>,
+    ) -> Self {
+        NumberFormatting {
+            grouping_separator,
+            grouping_size,
+            ordinal,
+            lang,
+        }
+    }
+}
+
 /// Generate a sequence of integers
 pub fn generate_integers<
     N: Node,
@@ -49,102 +74,75 @@ pub fn generate_integers<
 >(
     ctxt: &Context<N>,
     stctxt: &mut StaticContext<N, F, G, H>,
-    _start_at: &Transform<N>,
+    start_at: &Transform<N>,
     select: &Transform<N>,
     num: &Numbering<N>,
 ) -> Result<Sequence<N>, Error> {
-    // This implements "single" level. "multiple" and "any" are TODO
-    if num.level != Level::Single {
-        return Err(Error::new(
-            ErrorKind::NotImplemented,
-            "only single level is implemented",
-        ));
-    }
-
     // The select expression must evaluate to a single node item (XSLT error XTTE1000)
     let n = ctxt.dispatch(stctxt, select)?;
     if n.len() == 1 {
         if let Item::Node(m) = &n[0] {
             // Determine the count pattern
-            let count_pat =
-                (num.count)
-                    .clone()
-                    .unwrap_or(Pattern::Selection(match m.node_type() {
-                        NodeType::Element => PathBuilder::new()
-                            .step(
-                                Axis::SelfAxis,
-                                Axis::SelfAxis,
-                                NodeTest::Name(NameTest::new(
-                                    m.name().get_nsuri().map(WildcardOrName::Name),
-                                    None,
-                                    Some(WildcardOrName::Name(m.name().get_localname())),
-                                )),
-                            )
-                            .build(),
-                        NodeType::Text => PathBuilder::new()
-                            .step(
-                                Axis::SelfAxis,
-                                Axis::SelfAxis,
-                                NodeTest::Kind(KindTest::Text),
-                            )
-                            .build(),
-                        _ => {
-                            return Err(Error::new(
-                                ErrorKind::TypeError,
-                                "cannot match this type of node",
-                            ))
-                        }
-                    }));
-
-            // let a = $S/ancestor-or-self::node()[matches-count(.)][1]
-            // TODO: Don't Panic
-            let a = if count_pat.matches(ctxt, stctxt, &Item::Node(m.clone())) {
-                vec![m.clone()]
-            } else {
-                m.ancestor_iter()
-                    .filter(|i| count_pat.matches(ctxt, stctxt, &Item::Node(i.clone())))
-                    .take(1)
-                    .collect()
-            };
-            if a.is_empty() {
-                return Ok(vec![]);
-            }
-            // let f = $S/ancestor-or-self::node()[matches-from(.)][1]
-            // TODO: Don't Panic
-            let f: Vec<N> = if let Some(fr) = &num.from.clone() {
-                m.ancestor_iter()
-                    .filter(|i| {
-                        if i.node_type() == NodeType::Document {
-                            true
-                        } else {
-                            fr.matches(ctxt, stctxt, &Item::Node(i.clone()))
-                        }
-                    })
-                    .take(1)
-                    .collect()
-            } else {
-                // When there is no from pattern specified then use the root node
-                vec![m.owner_document().clone()]
+            let count_pat = match &num.count {
+                Some(c) => c.clone(),
+                None => Pattern::Selection(match m.node_type() {
+                    NodeType::Element => PathBuilder::new()
+                        .step(
+                            Axis::SelfAxis,
+                            Axis::SelfAxis,
+                            NodeTest::Name(NameTest::new(
+                                m.name().get_nsuri().map(WildcardOrName::Name),
+                                None,
+                                Some(WildcardOrName::Name(m.name().get_localname())),
+                            )),
+                        )
+                        .build(),
+                    NodeType::Text => PathBuilder::new()
+                        .step(
+                            Axis::SelfAxis,
+                            Axis::SelfAxis,
+                            NodeTest::Kind(KindTest::Text),
+                        )
+                        .build(),
+                    _ => {
+                        return Err(Error::new(
+                            ErrorKind::TypeError,
+                            "cannot match this type of node",
+                        ))
+                    }
+                }),
             };
-            if f.is_empty() {
-                return Ok(vec![]);
-            }
-            // let af = $a[ancestor-or-self::node()[. is $f]]
-            let af_test: Vec<N> = if a[0].is_same(&f[0]) {
-                vec![a[0].clone()]
-            } else {
-                a[0].ancestor_iter().filter(|i| i.is_same(&f[0])).collect()
+
+            let numbers = match num.level {
+                Level::Single => number_single(ctxt, stctxt, m, &count_pat, &num.from),
+                Level::Multiple => number_multiple(ctxt, stctxt, m, &count_pat, &num.from),
+                Level::Any => number_any(ctxt, stctxt, m, &count_pat, &num.from),
             };
-            let af = if af_test.is_empty() { vec![] } else { a };
-            if af.is_empty() {
-                return Ok(vec![]);
-            }
-            // 1 + count($af/preceding-sibling::node()[matches-count(.)])
-            let result: Vec<N> = af[0]
-                .prev_iter()
-                .filter(|i| count_pat.matches(ctxt, stctxt, &Item::Node(i.clone())))
-                .collect();
-            Ok(vec![Item::Value(Rc::new(Value::from(1 + result.len())))])
+
+            // start-at is a whitespace-separated list of integers.
+            // The last value applies to any remaining numbers.
+            let start = ctxt
+                .dispatch(stctxt, start_at)?
+                .to_string()
+                .split_whitespace()
+                .map(|s| {
+                    s.parse::<i64>().map_err(|_| {
+                        Error::new(
+                            ErrorKind::TypeError,
+                            format!("start-at value \"{}\" is not an integer", s),
+                        )
+                    })
+                })
+                .collect::<Result<Vec<i64>, Error>>()?;
+
+            Ok(numbers
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    let offset = start.get(i).or(start.last()).map_or(0, |s| s - 1);
+                    Item::Value(Rc::new(Value::from(*v as i64 + offset)))
+                })
+                .collect())
         } else {
             Err(Error::new_with_code(
                 ErrorKind::TypeError,
@@ -161,6 +159,167 @@ pub fn generate_integers<
     }
 }
 
+/// Does the node match the from pattern? The root node always matches.
+fn matches_from<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    n: &N,
+    from: &Option<Pattern<N>>,
+) -> bool {
+    n.node_type() == NodeType::Document
+        || from
+            .as_ref()
+            .is_some_and(|fr| fr.matches(ctxt, stctxt, &Item::Node(n.clone())))
+}
+
+/// Number a node for level="single". See XSLT 12.3.
+fn number_single<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    m: &N,
+    count_pat: &Pattern<N>,
+    from: &Option<Pattern<N>>,
+) -> Vec<usize> {
+    // let a = $S/ancestor-or-self::node()[matches-count(.)][1]
+    let a = if count_pat.matches(ctxt, stctxt, &Item::Node(m.clone())) {
+        vec![m.clone()]
+    } else {
+        m.ancestor_iter()
+            .filter(|i| count_pat.matches(ctxt, stctxt, &Item::Node(i.clone())))
+            .take(1)
+            .collect()
+    };
+    if a.is_empty() {
+        return vec![];
+    }
+    // let f = $S/ancestor-or-self::node()[matches-from(.)][1]
+    let f: Vec<N> = if from.is_some() {
+        m.ancestor_iter()
+            .filter(|i| matches_from(ctxt, stctxt, i, from))
+            .take(1)
+            .collect()
+    } else {
+        // When there is no from pattern specified then use the root node
+        vec![m.owner_document().clone()]
+    };
+    if f.is_empty() {
+        return vec![];
+    }
+    // let af = $a[ancestor-or-self::node()[. is $f]]
+    let af_test: Vec<N> = if a[0].is_same(&f[0]) {
+        vec![a[0].clone()]
+    } else {
+        a[0].ancestor_iter().filter(|i| i.is_same(&f[0])).collect()
+    };
+    if af_test.is_empty() {
+        return vec![];
+    }
+    // 1 + count($af/preceding-sibling::node()[matches-count(.)])
+    vec![
+        1 + a[0]
+            .prev_iter()
+            .filter(|i| count_pat.matches(ctxt, stctxt, &Item::Node(i.clone())))
+            .count(),
+    ]
+}
+
+/// Number a node for level="multiple". See XSLT 12.3.
+fn number_multiple<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    m: &N,
+    count_pat: &Pattern<N>,
+    from: &Option<Pattern<N>>,
+) -> Vec<usize> {
+    // let af = $S/ancestor-or-self::node()[matches-count(.)]
+    //            [ancestor-or-self::node()[. is $f]]
+    // The ancestors are visited nearest first, stopping at $f.
+    let mut af = vec![];
+    for a in std::iter::once(m.clone()).chain(m.ancestor_iter()) {
+        if count_pat.matches(ctxt, stctxt, &Item::Node(a.clone())) {
+            af.push(a.clone())
+        }
+        if matches_from(ctxt, stctxt, &a, from) {
+            break;
+        }
+    }
+    // for $af in $af return 1 + count($af/preceding-sibling::node()[matches-count(.)])
+    af.iter()
+        .rev()
+        .map(|a| {
+            1 + a
+                .prev_iter()
+                .filter(|i| count_pat.matches(ctxt, stctxt, &Item::Node(i.clone())))
+                .count()
+        })
+        .collect()
+}
+
+/// Number a node for level="any". See XSLT 12.3.
+fn number_any<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    m: &N,
+    count_pat: &Pattern<N>,
+    from: &Option<Pattern<N>>,
+) -> Vec<usize> {
+    // let a = $S/(preceding::node()|ancestor-or-self::node())[matches-count(.)]
+    // let f = $S/(preceding::node()|ancestor-or-self::node())[matches-from(.)][last()]
+    // count($a[. is $f or . >> $f])
+    // Walk the ancestor-or-self and preceding axes in reverse document order, i.e. for each ancestor-or-self node
+    // its preceding siblings and their descendants come before it. Stop at the nearest node that matches the from pattern.
+    let mut count = 0;
+    let mut cur = m.clone();
+    'walk: loop {
+        // An attribute is not a sibling of its element's children, so only its ancestors are visited
+        let mut nodes = vec![];
+        if !matches!(cur.node_type(), NodeType::Attribute | NodeType::Namespace) {
+            cur.prev_iter().for_each(|s| {
+                let mut subtree = vec![s.clone()];
+                subtree.extend(s.descend_iter());
+                nodes.extend(subtree.into_iter().rev());
+            })
+        }
+        for d in std::iter::once(cur.clone()).chain(nodes) {
+            if count_pat.matches(ctxt, stctxt, &Item::Node(d.clone())) {
+                count += 1
+            }
+            if matches_from(ctxt, stctxt, &d, from) {
+                break 'walk;
+            }
+        }
+        match cur.parent() {
+            Some(p) => cur = p,
+            None => break,
+        }
+    }
+    if count == 0 {
+        vec![]
+    } else {
+        vec![count]
+    }
+}
+
 /// XPath number function.
 pub fn number<
     N: Node,
@@ -422,7 +581,7 @@ pub fn format_number<
     }
 }
 
-/// XSLT xsl:number and XPath format-integer function.
+/// XPath format-integer function.
 pub fn format_integer<
     N: Node,
     F: FnMut(&str) -> Result<(), Error>,
@@ -435,169 +594,433 @@ pub fn format_integer<
     picture: &Transform<N>,
 ) -> Result<Sequence<N>, Error> {
     let p = ctxt.dispatch(stctxt, picture)?.to_string();
-    let numbers = ctxt.dispatch(stctxt, num)?;
-    let mut nit = numbers.iter();
-
-    let mut result = String::new();
-
-    // Interpret the picture string.
-    // Most of the tokens are one character, except for 'Ww'.
-    let mut pit = p.chars().peekable();
-    loop {
-        let c = pit.next();
-        if let Some(d) = c {
-            if d.is_alphanumeric() {
-                match d {
-                    '0' => {
-                        // 01, 02, 03, 04, ...
-                        // length specification
-                        // TODO: non-arabic-roman numerals
-                        let mut token = String::from(d);
-                        loop {
-                            if let Some(p) = pit.peek() {
-                                if p.eq(&'0') {
-                                    pit.next();
-                                    token.push('0');
-                                } else if p.eq(&'1') {
-                                    pit.next();
-                                    token.push('1');
-                                } else {
-                                    break;
-                                }
-                            } else {
-                                break;
-                            }
-                        }
-                        if let Some(num) = nit.next() {
-                            result.push_str(
-                                format!("{:0>1$}", num.to_int()?.to_string(), token.len()).as_str(),
-                            );
-                        } else {
-                            break;
-                        }
-                    }
-                    '1' => {
-                        // 1, 2, 3, ...
-                        if let Some(num) = nit.next() {
-                            result.push_str(num.to_int()?.to_string().as_str())
-                        } else {
-                            break;
-                        }
-                    }
-                    'A' => {
-                        // A, B, C, ..., AA, BB, CC, ...
-                    }
-                    'a' => {
-                        // a, b, c, ..., aa, bb, cc, ...
-                    }
-                    'i' => {
-                        // i, ii, iii, iv, v, vi, ...
-                        if let Some(num) = nit.next() {
-                            result.push_str(
-                                roman_converter(u16::try_from(num.to_int()?).map_err(|e| {
-                                    Error::new(ErrorKind::ParseError, e.to_string())
-                                })?)
-                                .map_err(|e| Error::new(ErrorKind::ParseError, e))?
-                                .to_lowercase()
-                                .as_str(),
-                            )
-                        } else {
-                            break;
-                        }
-                    }
-                    'I' => {
-                        // I, II, III, IV, V, VI, ...
-                        if let Some(num) = nit.next() {
-                            result.push_str(
-                                roman_converter(u16::try_from(num.to_int()?).map_err(|e| {
-                                    Error::new(ErrorKind::ParseError, e.to_string())
-                                })?)
-                                .map_err(|e| Error::new(ErrorKind::ParseError, e))?
-                                .as_str(),
-                            )
-                        } else {
-                            break;
-                        }
-                    }
-                    'w' => {
-                        // one, two, three, ...
-                        if let Some(num) = nit.next() {
-                            result.push_str(
-                                convert(
-                                    num.to_int()?,
-                                    Formatting {
-                                        title_case: false,
-                                        spaces: true,
-                                        conjunctions: false,
-                                        commas: false,
-                                        dashes: false,
-                                    },
-                                )
-                                .to_string()
-                                .as_str(),
-                            )
-                        } else {
-                            break;
-                        }
-                    }
-                    'W' => {
-                        // 'Ww'
-                        if let Some('w') = pit.peek() {
-                            // One, Two, Three, ...
-                            pit.next();
-                            if let Some(num) = nit.next() {
-                                result.push_str(
-                                    convert(
-                                        num.to_int()?,
-                                        Formatting {
-                                            title_case: true,
-                                            spaces: true,
-                                            conjunctions: false,
-                                            commas: false,
-                                            dashes: false,
-                                        },
-                                    )
-                                    .to_string()
-                                    .as_str(),
-                                )
-                            } else {
-                                break;
-                            }
-                        } else {
-                            // ONE, TWO, THREE, ...
-                            if let Some(num) = nit.next() {
-                                result.push_str(
-                                    convert(
-                                        num.to_int()?,
-                                        Formatting {
-                                            title_case: false,
-                                            spaces: true,
-                                            conjunctions: false,
-                                            commas: false,
-                                            dashes: false,
-                                        },
-                                    )
-                                    .to_string()
-                                    .to_uppercase()
-                                    .as_str(),
-                                )
-                            } else {
-                                break;
-                            }
-                        }
-                    }
-                    // TODO: non-English words
-                    // Use french-numbers crate
-                    // Use italian-numbers crate
-                    _ => {}
-                }
+    let numbers = ctxt
+        .dispatch(stctxt, num)?
+        .iter()
+        .map(|i| i.to_int())
+        .collect::<Result<Vec<i64>, Error>>()?;
+    if numbers.is_empty() {
+        return Ok(vec![Item::Value(Rc::new(Value::from("")))]);
+    }
+
+    // The picture may have a format modifier, for example "1;o"
+    let (primary, ordinal) = match p.rsplit_once(';') {
+        Some((q, m)) => (q, m.starts_with('o')),
+        None => (p.as_str(), false),
+    };
+
+    Ok(vec![Item::Value(Rc::new(Value::from(
+        format_place_markers(
+            &numbers,
+            primary,
+            &MarkerOptions {
+                grouping: None,
+                ordinal,
+                lang: Language::English,
+            },
+        ),
+    )))])
+}
+
+/// XSLT xsl:number. Formats the place markers according to the format attribute and
+/// the remaining formatting attributes. See XSLT 12.4.
+pub fn format_numbering<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    num: &Transform<N>,
+    picture: &Transform<N>,
+    fmt: &NumberFormatting<N>,
+) -> Result<Sequence<N>, Error> {
+    let p = ctxt.dispatch(stctxt, picture)?.to_string();
+    let numbers = ctxt
+        .dispatch(stctxt, num)?
+        .iter()
+        .map(|i| i.to_int())
+        .collect::<Result<Vec<i64>, Error>>()?;
+
+    // Grouping only happens when both the separator and size are given
+    let grouping = match (&fmt.grouping_separator, &fmt.grouping_size) {
+        (Some(sep), Some(size)) => {
+            let sep = ctxt.dispatch(stctxt, sep)?.to_string();
+            let size = ctxt.dispatch(stctxt, size)?.to_string();
+            match size.trim().parse::<usize>() {
+                Ok(s) if s > 0 && !sep.is_empty() => Some((sep, s)),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+    let ordinal = match &fmt.ordinal {
+        Some(o) => {
+            let o = ctxt.dispatch(stctxt, o)?.to_string();
+            !o.is_empty() && o != "no"
+        }
+        None => false,
+    };
+    // Words and ordinals are available in English and German.
+    // Any other language falls back to English, as allowed by XSLT 12.4.
+    let lang = match &fmt.lang {
+        Some(l) => {
+            let l = ctxt.dispatch(stctxt, l)?.to_string();
+            if !is_language(&l) {
+                return Err(Error::new_with_code(
+                    ErrorKind::DynamicAbsent,
+                    format!("lang value \"{}\" is not a language code", l),
+                    Some(QualifiedName::new(None, None, "XTDE0030")),
+                ));
+            }
+            Language::from_code(&l)
+        }
+        None => Language::English,
+    };
+
+    Ok(vec![Item::Value(Rc::new(Value::from(
+        format_place_markers(
+            &numbers,
+            &p,
+            &MarkerOptions {
+                grouping,
+                ordinal,
+                lang,
+            },
+        ),
+    )))])
+}
+
+/// Options for formatting place markers.
+struct MarkerOptions {
+    /// Separator and group size for decimal digits
+    grouping: Option<(String, usize)>,
+    ordinal: bool,
+    lang: Language,
+}
+
+/// The languages that numbers can be written in as words.
+#[derive(Clone, Copy, PartialEq)]
+enum Language {
+    English,
+    German,
+}
+impl Language {
+    /// Find the language for a language code. Unsupported languages are English.
+    fn from_code(code: &str) -> Self {
+        match code
+            .split('-')
+            .next()
+            .map(|c| c.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("de") => Language::German,
+            _ => Language::English,
+        }
+    }
+}
+
+// Is the string a valid xs:language value?
+fn is_language(s: &str) -> bool {
+    s.split('-').enumerate().all(|(i, p)| {
+        (1..=8).contains(&p.len())
+            && if i == 0 {
+                p.chars().all(|c| c.is_ascii_alphabetic())
             } else {
-                result.push(d)
+                p.chars().all(|c| c.is_ascii_alphanumeric())
             }
+    })
+}
+
+/// Format a list of numbers using a picture string.
+/// The picture consists of alternating format tokens (alphanumeric) and separators.
+/// A leading separator is a prefix and a trailing separator is a suffix.
+/// When there are more numbers than format tokens then the last format token, and its separator, are reused.
+fn format_place_markers(numbers: &[i64], picture: &str, opts: &MarkerOptions) -> String {
+    // Split the picture into runs of alphanumeric and non-alphanumeric characters
+    let mut runs: Vec<(bool, String)> = vec![];
+    picture.chars().for_each(|c| match runs.last_mut() {
+        Some((alnum, r)) if *alnum == c.is_alphanumeric() => r.push(c),
+        _ => runs.push((c.is_alphanumeric(), String::from(c))),
+    });
+
+    let mut prefix = String::new();
+    let mut suffix = String::new();
+    // Each format token, with the separator that precedes it
+    let mut tokens: Vec<(String, String)> = vec![];
+    let mut sep = String::new();
+    for (alnum, r) in runs {
+        if alnum {
+            tokens.push((sep.clone(), r));
+            sep.clear();
+        } else if tokens.is_empty() {
+            prefix = r;
         } else {
-            break;
+            sep = r;
+        }
+    }
+    if tokens.is_empty() {
+        tokens.push((String::new(), String::from("1")));
+    } else {
+        suffix = sep;
+    }
+
+    let mut result = prefix;
+    for (i, n) in numbers.iter().enumerate() {
+        let t = &tokens[i.min(tokens.len() - 1)];
+        if i > 0 {
+            if i < tokens.len() {
+                result.push_str(t.0.as_str())
+            } else if tokens.len() > 1 {
+                result.push_str(tokens[tokens.len() - 1].0.as_str())
+            } else {
+                result.push('.')
+            }
+        }
+        result.push_str(format_token(*n, t.1.as_str(), opts).as_str());
+    }
+    result.push_str(suffix.as_str());
+    result
+}
+
+/// Format a single number using a format token.
+fn format_token(n: i64, token: &str, opts: &MarkerOptions) -> String {
+    match token {
+        // A, B, C, ..., Z, AA, AB, AC, ...
+        "A" if n > 0 => alphabetic(n, 'A'),
+        // a, b, c, ..., z, aa, ab, ac, ...
+        "a" if n > 0 => alphabetic(n, 'a'),
+        // i, ii, iii, iv, v, vi, ...
+        "i" => roman(n).map_or_else(|| decimal(n, 1, opts), |r| r.to_lowercase()),
+        // I, II, III, IV, V, VI, ...
+        "I" => roman(n).unwrap_or_else(|| decimal(n, 1, opts)),
+        // one, two, three, ...
+        "w" => words(n, opts.ordinal, opts.lang),
+        // ONE, TWO, THREE, ...
+        "W" => words(n, opts.ordinal, opts.lang).to_uppercase(),
+        // One, Two, Three, ...
+        "Ww" => words(n, opts.ordinal, opts.lang)
+            .split(' ')
+            .map(|w| {
+                let mut c = w.chars();
+                c.next().map_or(String::new(), |f| {
+                    f.to_uppercase().chain(c).collect::<String>()
+                })
+            })
+            .collect::<Vec<String>>()
+            .join(" "),
+        // 1, 2, 3, ... or 01, 02, 03, ...
+        // TODO: non-arabic-roman numerals
+        t if t.ends_with('1') && t.chars().all(|c| c.is_ascii_digit()) => decimal(n, t.len(), opts),
+        // An unrecognised token is treated as "1"
+        _ => decimal(n, 1, opts),
+    }
+}
+
+fn decimal(n: i64, width: usize, opts: &MarkerOptions) -> String {
+    let digits = format!("{:0>1$}", n.unsigned_abs(), width);
+    let mut result = match &opts.grouping {
+        Some((sep, size)) => {
+            let mut g = String::new();
+            digits.chars().enumerate().for_each(|(i, c)| {
+                if i > 0 && (digits.len() - i) % size == 0 {
+                    g.push_str(sep)
+                }
+                g.push(c)
+            });
+            g
         }
+        None => digits,
+    };
+    if n < 0 {
+        result.insert(0, '-')
     }
+    if opts.ordinal {
+        result.push_str(match (n.unsigned_abs() % 10, n.unsigned_abs() % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        })
+    }
+    result
+}
+
+fn alphabetic(mut n: i64, base: char) -> String {
+    let mut result = vec![];
+    while n > 0 {
+        n -= 1;
+        result.push(char::from(base as u8 + (n % 26) as u8));
+        n /= 26;
+    }
+    result.iter().rev().collect()
+}
 
-    Ok(vec![Item::Value(Rc::new(Value::from(result)))])
+fn roman(n: i64) -> Option<String> {
+    u16::try_from(n).ok().and_then(|m| roman_converter(m).ok())
+}
+
+// TODO: other languages
+// Use french-numbers crate
+// Use italian-numbers crate
+fn words(n: i64, ordinal: bool, lang: Language) -> String {
+    if lang == Language::German {
+        return german_words(n, ordinal);
+    }
+    let w = convert(
+        n,
+        Formatting {
+            title_case: false,
+            spaces: true,
+            conjunctions: false,
+            commas: false,
+            dashes: false,
+        },
+    )
+    .to_string();
+    if ordinal {
+        let (init, last) = w.rsplit_once(' ').map_or(("", w.as_str()), |(i, l)| (i, l));
+        let last = match last {
+            "one" => String::from("first"),
+            "two" => String::from("second"),
+            "three" => String::from("third"),
+            "five" => String::from("fifth"),
+            "eight" => String::from("eighth"),
+            "nine" => String::from("ninth"),
+            "twelve" => String::from("twelfth"),
+            l if l.ends_with('y') => format!("{}ieth", &l[..l.len() - 1]),
+            l => format!("{}th", l),
+        };
+        if init.is_empty() {
+            last
+        } else {
+            format!("{} {}", init, last)
+        }
+    } else {
+        w
+    }
+}
+
+// German number words are written as one word, except for millions and above.
+fn german_words(n: i64, ordinal: bool) -> String {
+    if n < 0 {
+        format!("minus {}", german_number(n.unsigned_abs(), ordinal))
+    } else {
+        german_number(n as u64, ordinal)
+    }
+}
+
+fn german_number(n: u64, ordinal: bool) -> String {
+    if !ordinal {
+        return match n {
+            0 => String::from("null"),
+            1 => String::from("eins"),
+            _ => german_cardinal(n),
+        };
+    }
+    // Ordinals below twenty end in "te", otherwise "ste"
+    let r = n % 100;
+    if (1..20).contains(&r) {
+        let small = match r {
+            1 => String::from("erste"),
+            3 => String::from("dritte"),
+            7 => String::from("siebte"),
+            8 => String::from("achte"),
+            _ => format!("{}te", german_cardinal(r)),
+        };
+        let head = german_cardinal(n - r);
+        if head.is_empty() {
+            small
+        } else if (n - r) % 1_000_000 == 0 {
+            // Millions and above are separate words
+            format!("{} {}", head, small)
+        } else {
+            format!("{}{}", head, small)
+        }
+    } else {
+        // "millionen" becomes "millionste", "milliarde" becomes "milliardste"
+        let c = german_cardinal(n);
+        let stem = c
+            .strip_suffix("en")
+            .or_else(|| c.strip_suffix('e'))
+            .unwrap_or(&c);
+        format!("{}ste", stem)
+    }
+}
+
+// The German words for a number, without the special case for one. Zero is the empty string.
+fn german_cardinal(n: u64) -> String {
+    const UNITS: [&str; 20] = [
+        "",
+        "ein",
+        "zwei",
+        "drei",
+        "vier",
+        "fünf",
+        "sechs",
+        "sieben",
+        "acht",
+        "neun",
+        "zehn",
+        "elf",
+        "zwölf",
+        "dreizehn",
+        "vierzehn",
+        "fünfzehn",
+        "sechzehn",
+        "siebzehn",
+        "achtzehn",
+        "neunzehn",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig", "achtzig",
+        "neunzig",
+    ];
+    match n {
+        0..=19 => String::from(UNITS[n as usize]),
+        20..=99 => match n % 10 {
+            0 => String::from(TENS[(n / 10) as usize]),
+            u => format!("{}und{}", UNITS[u as usize], TENS[(n / 10) as usize]),
+        },
+        100..=999 => format!(
+            "{}hundert{}",
+            UNITS[(n / 100) as usize],
+            german_rest(n % 100)
+        ),
+        1_000..=999_999 => format!(
+            "{}tausend{}",
+            german_cardinal(n / 1_000),
+            german_rest(n % 1_000)
+        ),
+        _ => {
+            let (scale, singular, plural) = if n >= 1_000_000_000_000 {
+                (1_000_000_000_000, "billion", "billionen")
+            } else if n >= 1_000_000_000 {
+                (1_000_000_000, "milliarde", "milliarden")
+            } else {
+                (1_000_000, "million", "millionen")
+            };
+            let count = n / scale;
+            let head = if count == 1 {
+                format!("eine {}", singular)
+            } else {
+                format!("{} {}", german_cardinal(count), plural)
+            };
+            match n % scale {
+                0 => head,
+                rest => format!("{} {}", head, german_number(rest, false)),
+            }
+        }
+    }
+}
+
+// The last part of a compound number, where one is "eins"
+fn german_rest(n: u64) -> String {
+    if n == 1 {
+        String::from("eins")
+    } else {
+        german_cardinal(n)
+    }
 }
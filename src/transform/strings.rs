@@ -2,11 +2,13 @@
 
 use std::rc::Rc;
 
+use regex::{Captures, Regex};
 use unicode_segmentation::UnicodeSegmentation;
 use url::Url;
 
 use crate::item::{Item, Node, Sequence, SequenceTrait};
-use crate::transform::context::{Context, StaticContext};
+use crate::qname::QualifiedName;
+use crate::transform::context::{Context, ContextBuilder, StaticContext};
 use crate::transform::Transform;
 use crate::value::Value;
 use crate::xdmerror::{Error, ErrorKind};
@@ -387,3 +389,526 @@ pub(crate) fn tr_concat<
         Err(err) => Err(err),
     }
 }
+
+/// XPath matches function.
+pub fn matches<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    input: &Transform<N>,
+    pattern: &Transform<N>,
+    flags: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let s = ctxt.dispatch(stctxt, input)?.to_string();
+    let f = regex_flags(ctxt, stctxt, flags)?;
+    let re = compile_regex(ctxt.dispatch(stctxt, pattern)?.to_string().as_str(), &f)?;
+    Ok(vec![Item::Value(Rc::new(Value::from(re.is_match(&s))))])
+}
+
+/// XPath replace function.
+pub fn replace<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    input: &Transform<N>,
+    pattern: &Transform<N>,
+    replacement: &Transform<N>,
+    flags: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let s = ctxt.dispatch(stctxt, input)?.to_string();
+    let f = regex_flags(ctxt, stctxt, flags)?;
+    let re = compile_regex(ctxt.dispatch(stctxt, pattern)?.to_string().as_str(), &f)?;
+    if re.is_match("") {
+        return Err(regex_error(
+            "regular expression matches zero-length string",
+            "FORX0003",
+        ));
+    }
+    let r = parse_replacement(
+        ctxt.dispatch(stctxt, replacement)?.to_string().as_str(),
+        f.contains('q'),
+        re.captures_len() - 1,
+    )?;
+    Ok(vec![Item::Value(Rc::new(Value::from(
+        re.replace_all(&s, |caps: &Captures| {
+            r.iter().fold(String::new(), |mut acc, p| {
+                match p {
+                    Replacement::Literal(l) => acc.push_str(l),
+                    Replacement::Group(g) => acc.push_str(caps.get(*g).map_or("", |m| m.as_str())),
+                }
+                acc
+            })
+        })
+        .to_string(),
+    )))])
+}
+
+/// XPath tokenize function. When no pattern is given the input is split at whitespace.
+pub fn tokenize<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    input: &Transform<N>,
+    pattern: &Option<Box<Transform<N>>>,
+    flags: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let s = ctxt.dispatch(stctxt, input)?.to_string();
+    match pattern {
+        // Only XML whitespace separates tokens
+        None => Ok(s
+            .split([' ', '\t', '\n', '\r'])
+            .filter(|t| !t.is_empty())
+            .map(|t| Item::Value(Rc::new(Value::from(t))))
+            .collect()),
+        Some(p) => {
+            let f = regex_flags(ctxt, stctxt, flags)?;
+            let re = compile_regex(ctxt.dispatch(stctxt, p)?.to_string().as_str(), &f)?;
+            if re.is_match("") {
+                return Err(regex_error(
+                    "regular expression matches zero-length string",
+                    "FORX0003",
+                ));
+            }
+            if s.is_empty() {
+                return Ok(vec![]);
+            }
+            Ok(re
+                .split(&s)
+                .map(|t| Item::Value(Rc::new(Value::from(t))))
+                .collect())
+        }
+    }
+}
+
+/// Evaluate the flags argument of a regular expression function.
+pub(crate) fn regex_flags<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    flags: &Option<Box<Transform<N>>>,
+) -> Result<String, Error> {
+    flags.as_ref().map_or(Ok(String::new()), |f| {
+        Ok(ctxt.dispatch(stctxt, f)?.to_string())
+    })
+}
+
+fn regex_error(msg: &str, code: &str) -> Error {
+    Error::new_with_code(
+        ErrorKind::ParseError,
+        msg,
+        Some(QualifiedName::new(None, None, code)),
+    )
+}
+
+/// Compile an XPath regular expression (see XPath Functions 5.6.1), with the given flags.
+/// The XPath syntax is translated to the syntax of the regex crate.
+pub(crate) fn compile_regex(pattern: &str, flags: &str) -> Result<Regex, Error> {
+    let (mut s, mut m, mut i, mut x, mut q) = (false, false, false, false, false);
+    for c in flags.chars() {
+        match c {
+            's' => s = true,
+            'm' => m = true,
+            'i' => i = true,
+            'x' => x = true,
+            'q' => q = true,
+            _ => {
+                return Err(regex_error(
+                    format!("invalid regular expression flag \"{}\"", c).as_str(),
+                    "FORX0001",
+                ))
+            }
+        }
+    }
+
+    let mut re = String::new();
+    if i {
+        re.push_str("(?i)")
+    }
+    if q {
+        // All other flags are ignored
+        re.push_str(regex::escape(pattern).as_str());
+    } else {
+        if m {
+            re.push_str("(?m)")
+        }
+        if s {
+            re.push_str("(?s)")
+        }
+        let mut class = 0;
+        let mut it = pattern.chars().peekable();
+        while let Some(c) = it.next() {
+            match c {
+                '\\' => match it.next() {
+                    // Multi-character escapes for XML names
+                    Some('i') => re.push_str(r"[\p{L}\p{Nl}_:]"),
+                    Some('I') => re.push_str(r"[^\p{L}\p{Nl}_:]"),
+                    Some('c') => re.push_str(r"[\p{L}\p{Nl}\p{Mn}\p{Mc}\p{Nd}._:\-\x{B7}]"),
+                    Some('C') => re.push_str(r"[^\p{L}\p{Nl}\p{Mn}\p{Mc}\p{Nd}._:\-\x{B7}]"),
+                    // The other multi-character escapes differ from their Unicode meaning in the regex crate
+                    Some('s') => re.push_str(r"[ \t\n\r]"),
+                    Some('S') => re.push_str(r"[^ \t\n\r]"),
+                    Some('w') => re.push_str(r"[^\p{P}\p{Z}\p{C}]"),
+                    Some('W') => re.push_str(r"[\p{P}\p{Z}\p{C}]"),
+                    Some('d') => re.push_str(r"\p{Nd}"),
+                    Some('D') => re.push_str(r"\P{Nd}"),
+                    // Unicode block escapes are given as ranges of characters
+                    Some(e) if (e == 'p' || e == 'P') && it.peek() == Some(&'{') => {
+                        let name: String = it.by_ref().take_while(|c| *c != '}').collect();
+                        match name.strip_prefix("{Is") {
+                            Some(block) => re.push_str(unicode_block(block, e == 'P')?.as_str()),
+                            None => {
+                                re.push('\\');
+                                re.push(e);
+                                re.push_str(name.as_str());
+                                re.push('}')
+                            }
+                        }
+                    }
+                    Some(d) if d.is_ascii_digit() && class == 0 => {
+                        return Err(regex_error("back-references are not supported", "FORX0002"))
+                    }
+                    Some(e) => {
+                        re.push('\\');
+                        re.push(e)
+                    }
+                    None => return Err(regex_error("trailing escape character", "FORX0002")),
+                },
+                '[' => {
+                    // Character class subtraction is "-[" in XPath, but "--[" in the regex crate
+                    if class > 0 && re.ends_with('-') && !re.ends_with(r"\-") {
+                        re.push('-')
+                    }
+                    class += 1;
+                    re.push('[');
+                    if it.peek() == Some(&'^') {
+                        re.push(it.next().unwrap())
+                    }
+                }
+                ']' if class > 0 => {
+                    class -= 1;
+                    re.push(']')
+                }
+                // "&&", "~~" and "--" are operators in a character class of the regex crate,
+                // but these characters are literals in XPath, other than "-" in a range or subtraction
+                '&' | '~' if class > 0 => {
+                    re.push('\\');
+                    re.push(c)
+                }
+                '-' if class > 0
+                    && it.peek() != Some(&'[')
+                    && (re.ends_with('[')
+                        || re.ends_with("[^")
+                        || re.ends_with('-')
+                        || it.peek() == Some(&']')) =>
+                {
+                    re.push_str(r"\-")
+                }
+                // Without the s flag, '.' does not match newline or carriage return
+                '.' if class == 0 && !s => re.push_str(r"[^\n\r]"),
+                // The x flag removes whitespace, except within a character class
+                w if x && class == 0 && (w == ' ' || w == '\t' || w == '\n' || w == '\r') => {}
+                _ => re.push(c),
+            }
+        }
+    }
+    Regex::new(re.as_str()).map_err(|e| {
+        regex_error(
+            format!("invalid regular expression: {}", e).as_str(),
+            "FORX0002",
+        )
+    })
+}
+
+// The Unicode blocks that may be used in a block escape, from XML Schema Part 2 F.1.1.
+const UNICODE_BLOCKS: &[(&str, &[(u32, u32)])] = &[
+    ("BasicLatin", &[(0x0000, 0x007F)]),
+    ("Latin-1Supplement", &[(0x0080, 0x00FF)]),
+    ("LatinExtended-A", &[(0x0100, 0x017F)]),
+    ("LatinExtended-B", &[(0x0180, 0x024F)]),
+    ("IPAExtensions", &[(0x0250, 0x02AF)]),
+    ("SpacingModifierLetters", &[(0x02B0, 0x02FF)]),
+    ("CombiningDiacriticalMarks", &[(0x0300, 0x036F)]),
+    ("Greek", &[(0x0370, 0x03FF)]),
+    ("Cyrillic", &[(0x0400, 0x04FF)]),
+    ("Armenian", &[(0x0530, 0x058F)]),
+    ("Hebrew", &[(0x0590, 0x05FF)]),
+    ("Arabic", &[(0x0600, 0x06FF)]),
+    ("Syriac", &[(0x0700, 0x074F)]),
+    ("Thaana", &[(0x0780, 0x07BF)]),
+    ("Devanagari", &[(0x0900, 0x097F)]),
+    ("Bengali", &[(0x0980, 0x09FF)]),
+    ("Gurmukhi", &[(0x0A00, 0x0A7F)]),
+    ("Gujarati", &[(0x0A80, 0x0AFF)]),
+    ("Oriya", &[(0x0B00, 0x0B7F)]),
+    ("Tamil", &[(0x0B80, 0x0BFF)]),
+    ("Telugu", &[(0x0C00, 0x0C7F)]),
+    ("Kannada", &[(0x0C80, 0x0CFF)]),
+    ("Malayalam", &[(0x0D00, 0x0D7F)]),
+    ("Sinhala", &[(0x0D80, 0x0DFF)]),
+    ("Thai", &[(0x0E00, 0x0E7F)]),
+    ("Lao", &[(0x0E80, 0x0EFF)]),
+    ("Tibetan", &[(0x0F00, 0x0FFF)]),
+    ("Myanmar", &[(0x1000, 0x109F)]),
+    ("Georgian", &[(0x10A0, 0x10FF)]),
+    ("HangulJamo", &[(0x1100, 0x11FF)]),
+    ("Ethiopic", &[(0x1200, 0x137F)]),
+    ("Cherokee", &[(0x13A0, 0x13FF)]),
+    ("UnifiedCanadianAboriginalSyllabics", &[(0x1400, 0x167F)]),
+    ("Ogham", &[(0x1680, 0x169F)]),
+    ("Runic", &[(0x16A0, 0x16FF)]),
+    ("Khmer", &[(0x1780, 0x17FF)]),
+    ("Mongolian", &[(0x1800, 0x18AF)]),
+    ("LatinExtendedAdditional", &[(0x1E00, 0x1EFF)]),
+    ("GreekExtended", &[(0x1F00, 0x1FFF)]),
+    ("GeneralPunctuation", &[(0x2000, 0x206F)]),
+    ("SuperscriptsandSubscripts", &[(0x2070, 0x209F)]),
+    ("CurrencySymbols", &[(0x20A0, 0x20CF)]),
+    ("CombiningMarksforSymbols", &[(0x20D0, 0x20FF)]),
+    ("LetterlikeSymbols", &[(0x2100, 0x214F)]),
+    ("NumberForms", &[(0x2150, 0x218F)]),
+    ("Arrows", &[(0x2190, 0x21FF)]),
+    ("MathematicalOperators", &[(0x2200, 0x22FF)]),
+    ("MiscellaneousTechnical", &[(0x2300, 0x23FF)]),
+    ("ControlPictures", &[(0x2400, 0x243F)]),
+    ("OpticalCharacterRecognition", &[(0x2440, 0x245F)]),
+    ("EnclosedAlphanumerics", &[(0x2460, 0x24FF)]),
+    ("BoxDrawing", &[(0x2500, 0x257F)]),
+    ("BlockElements", &[(0x2580, 0x259F)]),
+    ("GeometricShapes", &[(0x25A0, 0x25FF)]),
+    ("MiscellaneousSymbols", &[(0x2600, 0x26FF)]),
+    ("Dingbats", &[(0x2700, 0x27BF)]),
+    ("BraillePatterns", &[(0x2800, 0x28FF)]),
+    ("CJKRadicalsSupplement", &[(0x2E80, 0x2EFF)]),
+    ("KangxiRadicals", &[(0x2F00, 0x2FDF)]),
+    ("IdeographicDescriptionCharacters", &[(0x2FF0, 0x2FFF)]),
+    ("CJKSymbolsandPunctuation", &[(0x3000, 0x303F)]),
+    ("Hiragana", &[(0x3040, 0x309F)]),
+    ("Katakana", &[(0x30A0, 0x30FF)]),
+    ("Bopomofo", &[(0x3100, 0x312F)]),
+    ("HangulCompatibilityJamo", &[(0x3130, 0x318F)]),
+    ("Kanbun", &[(0x3190, 0x319F)]),
+    ("BopomofoExtended", &[(0x31A0, 0x31BF)]),
+    ("EnclosedCJKLettersandMonths", &[(0x3200, 0x32FF)]),
+    ("CJKCompatibility", &[(0x3300, 0x33FF)]),
+    ("CJKUnifiedIdeographsExtensionA", &[(0x3400, 0x4DB5)]),
+    ("CJKUnifiedIdeographs", &[(0x4E00, 0x9FFF)]),
+    ("YiSyllables", &[(0xA000, 0xA48F)]),
+    ("YiRadicals", &[(0xA490, 0xA4CF)]),
+    ("HangulSyllables", &[(0xAC00, 0xD7A3)]),
+    // Surrogates are not characters, so these blocks match nothing
+    ("HighSurrogates", &[]),
+    ("HighPrivateUseSurrogates", &[]),
+    ("LowSurrogates", &[]),
+    ("PrivateUse", &[(0xE000, 0xF8FF)]),
+    ("CJKCompatibilityIdeographs", &[(0xF900, 0xFAFF)]),
+    ("AlphabeticPresentationForms", &[(0xFB00, 0xFB4F)]),
+    ("ArabicPresentationForms-A", &[(0xFB50, 0xFDFF)]),
+    ("CombiningHalfMarks", &[(0xFE20, 0xFE2F)]),
+    ("CJKCompatibilityForms", &[(0xFE30, 0xFE4F)]),
+    ("SmallFormVariants", &[(0xFE50, 0xFE6F)]),
+    ("ArabicPresentationForms-B", &[(0xFE70, 0xFEFE)]),
+    ("Specials", &[(0xFEFF, 0xFEFF), (0xFFF0, 0xFFFD)]),
+    ("HalfwidthandFullwidthForms", &[(0xFF00, 0xFFEF)]),
+    ("OldItalic", &[(0x10300, 0x1032F)]),
+    ("Gothic", &[(0x10330, 0x1034F)]),
+    ("Deseret", &[(0x10400, 0x1044F)]),
+    ("ByzantineMusicalSymbols", &[(0x1D000, 0x1D0FF)]),
+    ("MusicalSymbols", &[(0x1D100, 0x1D1FF)]),
+    ("MathematicalAlphanumericSymbols", &[(0x1D400, 0x1D7FF)]),
+    ("CJKUnifiedIdeographsExtensionB", &[(0x20000, 0x2A6D6)]),
+    (
+        "CJKCompatibilityIdeographsSupplement",
+        &[(0x2F800, 0x2FA1F)],
+    ),
+    ("Tags", &[(0xE0000, 0xE007F)]),
+    ("SupplementaryPrivateUseArea-A", &[(0xF0000, 0xFFFFD)]),
+    ("SupplementaryPrivateUseArea-B", &[(0x100000, 0x10FFFD)]),
+];
+
+// A character class for a Unicode block escape, \p{IsBlock} or its complement \P{IsBlock}.
+fn unicode_block(name: &str, complement: bool) -> Result<String, Error> {
+    let ranges = UNICODE_BLOCKS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, r)| *r)
+        .ok_or_else(|| {
+            regex_error(
+                format!("unknown Unicode block \"{}\"", name).as_str(),
+                "FORX0002",
+            )
+        })?;
+    if ranges.is_empty() {
+        // An empty class never matches, and its complement matches any character
+        return Ok(String::from(if complement {
+            r"[\x{0}-\x{10FFFF}]"
+        } else {
+            r"[^\x{0}-\x{10FFFF}]"
+        }));
+    }
+    let mut class = String::from(if complement { "[^" } else { "[" });
+    ranges
+        .iter()
+        .for_each(|(lo, hi)| class.push_str(format!(r"\x{{{:X}}}-\x{{{:X}}}", lo, hi).as_str()));
+    class.push(']');
+    Ok(class)
+}
+
+/// A component of a replacement string.
+enum Replacement {
+    Literal(String),
+    Group(usize),
+}
+
+/// Parse the replacement string for the replace function.
+/// Only "$N", "\$" and "\\" are significant, unless the replacement is to be used literally.
+fn parse_replacement(r: &str, literal: bool, groups: usize) -> Result<Vec<Replacement>, Error> {
+    if literal {
+        return Ok(vec![Replacement::Literal(r.to_string())]);
+    }
+    let mut result = vec![];
+    let mut lit = String::new();
+    let mut it = r.chars().peekable();
+    while let Some(c) = it.next() {
+        match c {
+            '\\' => match it.next() {
+                Some(e) if e == '\\' || e == '$' => lit.push(e),
+                _ => return Err(regex_error("invalid replacement string", "FORX0004")),
+            },
+            '$' => match it.next().and_then(|d| d.to_digit(10)) {
+                Some(d) => {
+                    // Use as many digits as make a valid group number
+                    let mut g = d as usize;
+                    while let Some(e) = it.peek().and_then(|e| e.to_digit(10)) {
+                        if g * 10 + (e as usize) > groups {
+                            break;
+                        }
+                        g = g * 10 + e as usize;
+                        it.next();
+                    }
+                    if !lit.is_empty() {
+                        result.push(Replacement::Literal(lit.clone()));
+                        lit.clear();
+                    }
+                    if g <= groups {
+                        result.push(Replacement::Group(g))
+                    }
+                }
+                None => return Err(regex_error("invalid replacement string", "FORX0004")),
+            },
+            _ => lit.push(c),
+        }
+    }
+    if !lit.is_empty() {
+        result.push(Replacement::Literal(lit))
+    }
+    Ok(result)
+}
+
+/// XSLT xsl:analyze-string. The input string is divided into matching and non-matching substrings.
+/// Each substring becomes the context item for the evaluation of the corresponding body.
+pub fn analyze_string<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    select: &Transform<N>,
+    regex: &Transform<N>,
+    flags: &Option<Box<Transform<N>>>,
+    matching: &Transform<N>,
+    non_matching: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let s = ctxt.dispatch(stctxt, select)?.to_string();
+    let f = regex_flags(ctxt, stctxt, flags)?;
+    let re = compile_regex(ctxt.dispatch(stctxt, regex)?.to_string().as_str(), &f)?;
+    if re.is_match("") {
+        return Err(regex_error(
+            "regular expression matches zero-length string",
+            "XTDE1150",
+        ));
+    }
+
+    // Each segment is a substring, along with its captured substrings if it is a match
+    let mut segments: Vec<(String, Option<Vec<String>>)> = vec![];
+    let mut last = 0;
+    for caps in re.captures_iter(&s) {
+        let m = caps.get(0).unwrap();
+        if m.start() > last {
+            segments.push((s[last..m.start()].to_string(), None))
+        }
+        segments.push((
+            m.as_str().to_string(),
+            Some(
+                caps.iter()
+                    .map(|c| c.map_or(String::new(), |d| d.as_str().to_string()))
+                    .collect(),
+            ),
+        ));
+        last = m.end();
+    }
+    if last < s.len() {
+        segments.push((s[last..].to_string(), None))
+    }
+
+    let seq: Sequence<N> = segments
+        .iter()
+        .map(|(t, _)| Item::Value(Rc::new(Value::from(t.as_str()))))
+        .collect();
+    let mut result = vec![];
+    for (i, (_, groups)) in segments.iter().enumerate() {
+        let (body, g) = match groups {
+            Some(g) => (matching, g.clone()),
+            None => (non_matching, vec![]),
+        };
+        let mut r = ContextBuilder::from(ctxt)
+            .context(seq.clone())
+            .index(i)
+            .regex_groups(g)
+            .build()
+            .dispatch(stctxt, body)?;
+        result.append(&mut r);
+    }
+    Ok(result)
+}
+
+/// XSLT regex-group function.
+pub fn regex_group<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    group: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let g = ctxt.dispatch(stctxt, group)?;
+    if g.len() != 1 {
+        return Err(Error::new(
+            ErrorKind::TypeError,
+            String::from("not a singleton sequence"),
+        ));
+    }
+    let i = usize::try_from(g[0].to_int()?).unwrap_or(usize::MAX);
+    Ok(vec![Item::Value(Rc::new(Value::from(
+        ctxt.regex_groups.get(i).map_or("", |s| s.as_str()),
+    )))])
+}
@@ -79,7 +79,7 @@ use crate::pattern::Pattern;
 use crate::qname::*;
 use crate::transform::callable::{ActualParameters, Callable, FormalParameters};
 use crate::transform::context::{Context, ContextBuilder};
-use crate::transform::numbers::{Level, Numbering};
+use crate::transform::numbers::{Level, NumberFormatting, Numbering};
 use crate::transform::template::Template;
 use crate::transform::{
     Axis, Grouping, KindTest, NameTest, NodeMatch, NodeTest, Order, Transform, WildcardOrName,
@@ -870,17 +870,12 @@ fn to_transform<N: Node>(
                 (Some(XSLTNS), "for-each") => {
                     let s = n.get_attribute(&QualifiedName::new(None, None, "select".to_string()));
                     if !s.to_string().is_empty() {
+                        let (sort_keys, body) = get_sort_keys_and_body(&n, ns, attr_sets)?;
                         Ok(Transform::ForEach(
                             None,
                             Box::new(parse::<N>(&s.to_string())?),
-                            Box::new(Transform::SequenceItems(n.child_iter().try_fold(
-                                vec![],
-                                |mut body, e| {
-                                    body.push(to_transform(e, ns, attr_sets)?);
-                                    Ok(body)
-                                },
-                            )?)),
-                            get_sort_keys(&n)?,
+                            Box::new(body),
+                            sort_keys,
                         ))
                     } else {
                         Result::Err(Error::new(
@@ -890,7 +885,7 @@ fn to_transform<N: Node>(
                     }
                 }
                 (Some(XSLTNS), "for-each-group") => {
-                    let ord = get_sort_keys(&n)?;
+                    let (ord, body) = get_sort_keys_and_body(&n, ns, attr_sets)?;
                     let s = n.get_attribute(&QualifiedName::new(None, None, "select".to_string()));
                     if !s.to_string().is_empty() {
                         match (
@@ -926,28 +921,27 @@ fn to_transform<N: Node>(
                             (by, "", "", "") => Ok(Transform::ForEach(
                                 Some(Grouping::By(vec![parse::<N>(by)?])),
                                 Box::new(parse::<N>(&s.to_string())?),
-                                Box::new(Transform::SequenceItems(n.child_iter().try_fold(
-                                    vec![],
-                                    |mut body, e| {
-                                        body.push(to_transform(e, ns, attr_sets)?);
-                                        Ok(body)
-                                    },
-                                )?)),
+                                Box::new(body),
                                 ord,
                             )),
                             ("", adj, "", "") => Ok(Transform::ForEach(
                                 Some(Grouping::Adjacent(vec![parse::<N>(adj)?])),
                                 Box::new(parse::<N>(&s.to_string())?),
-                                Box::new(Transform::SequenceItems(n.child_iter().try_fold(
-                                    vec![],
-                                    |mut body, e| {
-                                        body.push(to_transform(e, ns, attr_sets)?);
-                                        Ok(body)
-                                    },
-                                )?)),
+                                Box::new(body),
+                                ord,
+                            )),
+                            ("", "", sw, "") => Ok(Transform::ForEach(
+                                Some(Grouping::StartingWith(vec![Pattern::try_from(sw)?])),
+                                Box::new(parse::<N>(&s.to_string())?),
+                                Box::new(body),
+                                ord,
+                            )),
+                            ("", "", "", ew) => Ok(Transform::ForEach(
+                                Some(Grouping::EndingWith(vec![Pattern::try_from(ew)?])),
+                                Box::new(parse::<N>(&s.to_string())?),
+                                Box::new(body),
                                 ord,
                             )),
-                            // TODO: group-starting-with and group-ending-with
                             _ => Result::Err(Error::new(
                                 ErrorKind::NotImplemented,
                                 "invalid grouping attribute(s) specified".to_string(),
@@ -1161,32 +1155,105 @@ fn to_transform<N: Node>(
                         }),
                     ))
                 }
-                (Some(XSLTNS), "number") => {
-                    let value = n.get_attribute(&QualifiedName::new(None, None, "value"));
+                (Some(XSLTNS), "analyze-string") => {
                     let sel = n.get_attribute(&QualifiedName::new(None, None, "select"));
-                    let level = n.get_attribute(&QualifiedName::new(None, None, "level"));
-                    if level.to_string() != "" && level.to_string() != "single" {
+                    let regex = n.get_attribute(&QualifiedName::new(None, None, "regex"));
+                    let flags = n.get_attribute(&QualifiedName::new(None, None, "flags"));
+                    if sel.to_string().is_empty() || regex.to_string().is_empty() {
                         return Err(Error::new(
-                            ErrorKind::NotImplemented,
-                            "only single level numbering is supported",
+                            ErrorKind::TypeError,
+                            "missing select or regex attribute",
                         ));
                     }
+                    let mut matching = Transform::Empty;
+                    let mut non_matching = Transform::Empty;
+                    n.child_iter()
+                        .filter(|c| c.is_element())
+                        .try_for_each(|c| {
+                            let body = || {
+                                c.child_iter()
+                                    .try_fold(vec![], |mut body, e| {
+                                        body.push(to_transform(e, ns, attr_sets)?);
+                                        Ok::<Vec<Transform<N>>, Error>(body)
+                                    })
+                                    .map(Transform::SequenceItems)
+                            };
+                            match (c.name().get_nsuri_ref(), c.name().get_localname().as_str()) {
+                                (Some(XSLTNS), "matching-substring") => matching = body()?,
+                                (Some(XSLTNS), "non-matching-substring") => non_matching = body()?,
+                                (Some(XSLTNS), "fallback") => {}
+                                _ => {
+                                    return Err(Error::new(
+                                        ErrorKind::TypeError,
+                                        "invalid child of analyze-string",
+                                    ))
+                                }
+                            }
+                            Ok(())
+                        })?;
+                    Ok(Transform::AnalyzeString(
+                        Box::new(parse::<N>(&sel.to_string())?),
+                        Box::new(parse_avt(regex.to_string().as_str())?),
+                        if flags.to_string().is_empty() {
+                            None
+                        } else {
+                            Some(Box::new(parse_avt(flags.to_string().as_str())?))
+                        },
+                        Box::new(matching),
+                        Box::new(non_matching),
+                    ))
+                }
+                (Some(XSLTNS), "number") => {
+                    let value = n.get_attribute(&QualifiedName::new(None, None, "value"));
+                    let sel = n.get_attribute(&QualifiedName::new(None, None, "select"));
+                    let level = n.get_attribute(&QualifiedName::new(None, None, "level"));
                     let count = n.get_attribute(&QualifiedName::new(None, None, "count"));
                     let from = n.get_attribute(&QualifiedName::new(None, None, "from"));
                     let format = n.get_attribute(&QualifiedName::new(None, None, "format"));
-                    // TODO: lang, letter-value, ordinal, start-at, grouping-separator, grouping-size
+                    // The remaining attributes are all attribute value templates
+                    let avt = |a: &str| -> Result<Option<Transform<N>>, Error> {
+                        let v = n.get_attribute(&QualifiedName::new(None, None, a));
+                        if v.to_string().is_empty() {
+                            Ok(None)
+                        } else {
+                            Ok(Some(parse_avt(v.to_string().as_str())?))
+                        }
+                    };
+                    let formatting = NumberFormatting::new(
+                        avt("grouping-separator")?,
+                        avt("grouping-size")?,
+                        avt("ordinal")?,
+                        avt("lang")?,
+                    );
+                    let format = Box::new(if format.to_string().is_empty() {
+                        Transform::Literal(Item::Value(Rc::new(Value::from("1"))))
+                    } else {
+                        parse_avt(format.to_string().as_str())?
+                    });
+                    // TODO: letter-value
                     if value.to_string().is_empty() {
                         // Compute place marker
-                        Ok(Transform::FormatInteger(
+                        Ok(Transform::FormatNumbering(
                             Box::new(Transform::GenerateIntegers(
-                                Box::new(Transform::Empty), // start-at (TODO)
+                                Box::new(avt("start-at")?.unwrap_or(Transform::Empty)),
                                 Box::new(if sel.to_string().is_empty() {
                                     Transform::ContextItem
                                 } else {
                                     parse::<N>(&sel.to_string())?
                                 }), // select
                                 Box::new(Numbering::new(
-                                    Level::Single, // TODO: parse level attribute value
+                                    match level.to_string().as_str() {
+                                        "" | "single" => Level::Single,
+                                        "multiple" => Level::Multiple,
+                                        "any" => Level::Any,
+                                        l => {
+                                            return Err(Error::new_with_code(
+                                                ErrorKind::StaticAbsent,
+                                                format!("invalid value \"{}\" for level", l),
+                                                Some(QualifiedName::new(None, None, "XTSE0020")),
+                                            ))
+                                        }
+                                    },
                                     if count.to_string().is_empty() {
                                         None
                                     } else {
@@ -1199,25 +1266,15 @@ fn to_transform<N: Node>(
                                     },
                                 )),
                             )),
-                            Box::new(Transform::Literal(Item::Value(
-                                if format.to_string().is_empty() {
-                                    Rc::new(Value::from("1"))
-                                } else {
-                                    format
-                                },
-                            ))),
+                            format,
+                            Box::new(formatting),
                         ))
                     } else {
                         // Place marker is supplied
-                        Ok(Transform::FormatInteger(
+                        Ok(Transform::FormatNumbering(
                             Box::new(parse::<N>(&value.to_string())?),
-                            Box::new(Transform::Literal(Item::Value(
-                                if format.to_string().is_empty() {
-                                    Rc::new(Value::from("1"))
-                                } else {
-                                    format
-                                },
-                            ))),
+                            format,
+                            Box::new(formatting),
                         ))
                     }
                 }
@@ -1297,13 +1354,7 @@ fn get_sort_keys<N: Node>(n: &N) -> Result<Vec<(Order, Transform<N>)>, Error> {
         .try_fold(vec![], |mut acc, c| match c.node_type() {
             NodeType::Element => {
                 if c.name() == QualifiedName::new(Some(XSLTNS.to_string()), None, "sort") {
-                    let ordval = c.get_attribute(&QualifiedName::new(None, None, "order"));
-                    let ord = match ordval.to_string().as_str() {
-                        "descending" => Order::Descending,
-                        _ => Order::Ascending,
-                    };
-                    let sortsel = c.get_attribute(&QualifiedName::new(None, None, "select"));
-                    acc.push((ord, parse::<N>(&sortsel.to_string())?));
+                    acc.push(get_sort_key(&c)?);
                     Ok(acc)
                 } else {
                     Err(Error::new(
@@ -1335,6 +1386,39 @@ fn get_sort_keys<N: Node>(n: &N) -> Result<Vec<(Order, Transform<N>)>, Error> {
         })
 }
 
+type SortKeys<N> = Vec<(Order, Transform<N>)>;
+
+fn get_sort_key<N: Node>(c: &N) -> Result<(Order, Transform<N>), Error> {
+    let ordval = c.get_attribute(&QualifiedName::new(None, None, "order"));
+    let ord = match ordval.to_string().as_str() {
+        "descending" => Order::Descending,
+        _ => Order::Ascending,
+    };
+    let sortsel = c.get_attribute(&QualifiedName::new(None, None, "select"));
+    Ok((ord, parse::<N>(&sortsel.to_string())?))
+}
+
+/// Compile the content of an instruction that may begin with xsl:sort elements, such as xsl:for-each.
+/// Returns the sort keys and the sequence constructor made from the remaining content.
+fn get_sort_keys_and_body<N: Node>(
+    n: &N,
+    ns: &Vec<HashMap<String, String>>,
+    attr_sets: &HashMap<QualifiedName, Vec<Transform<N>>>,
+) -> Result<(SortKeys<N>, Transform<N>), Error> {
+    let mut sort_keys = vec![];
+    let mut body = vec![];
+    n.child_iter().try_for_each(|c| {
+        if c.is_element() && c.name() == QualifiedName::new(Some(XSLTNS.to_string()), None, "sort")
+        {
+            sort_keys.push(get_sort_key(&c)?)
+        } else {
+            body.push(to_transform(c, ns, attr_sets)?)
+        }
+        Ok::<(), Error>(())
+    })?;
+    Ok((sort_keys, Transform::SequenceItems(body)))
+}
+
 /// Strip whitespace nodes from a XDM tree.
 /// See [XSLT 4.3](https://www.w3.org/TR/2017/REC-xslt-30-20170608/#stylesheet-stripping).
 /// The [Node] argument must be the document node of the tree.
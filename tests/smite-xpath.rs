@@ -336,6 +336,50 @@ fn xpath_fncall_translate() {
         .expect("test failed")
 }
 #[test]
+fn xpath_fncall_matches() {
+    xpathgeneric::generic_fncall_matches::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_fncall_matches_flags() {
+    xpathgeneric::generic_fncall_matches_flags::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_fncall_matches_escapes() {
+    xpathgeneric::generic_fncall_matches_escapes::<RNode, _, _>(
+        smite::make_empty_doc,
+        smite::make_sd,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xpath_fncall_matches_bad_flag() {
+    xpathgeneric::generic_fncall_matches_bad_flag::<RNode, _, _>(
+        smite::make_empty_doc,
+        smite::make_sd,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xpath_fncall_replace() {
+    xpathgeneric::generic_fncall_replace::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_fncall_replace_zero_length() {
+    xpathgeneric::generic_fncall_replace_zero_length::<RNode, _, _>(
+        smite::make_empty_doc,
+        smite::make_sd,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xpath_fncall_tokenize() {
+    xpathgeneric::generic_fncall_tokenize::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
 fn xpath_fncall_boolean_true() {
     xpathgeneric::generic_fncall_boolean_true::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
         .expect("test failed")
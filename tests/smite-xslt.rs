@@ -203,6 +203,51 @@ fn xslt_number_1() {
     .expect("test failed")
 }
 #[test]
+fn xslt_number_multiple() {
+    xsltgeneric::generic_number_multiple(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_sd_cooked,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xslt_number_any() {
+    xsltgeneric::generic_number_any(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_sd_cooked,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xslt_number_formatting() {
+    xsltgeneric::generic_number_formatting(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_sd_cooked,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xslt_analyze_string() {
+    xsltgeneric::generic_analyze_string(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_sd_cooked,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xslt_analyze_string_flags() {
+    xsltgeneric::generic_analyze_string_flags(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_sd_cooked,
+    )
+    .expect("test failed")
+}
+#[test]
 fn xslt_attr_set_1() {
     xsltgeneric::attr_set_1(
         smite::make_from_str,
@@ -229,3 +274,75 @@ fn xslt_attr_set_3() {
     )
     .expect("test failed")
 }
+#[test]
+fn xslt_group_starting_with() {
+    xsltgeneric::generic_group_starting_with(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_sd_cooked,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xslt_group_ending_with() {
+    xsltgeneric::generic_group_ending_with(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_sd_cooked,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xslt_group_starting_with_sort() {
+    xsltgeneric::generic_group_starting_with_sort(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_sd_cooked,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xslt_group_starting_with_sort_error() {
+    xsltgeneric::generic_group_starting_with_sort_error(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_sd_cooked,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xslt_number_any_attribute() {
+    xsltgeneric::generic_number_any_attribute(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_sd_cooked,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xslt_number_lang() {
+    xsltgeneric::generic_number_lang(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_sd_cooked,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xslt_number_min_integer() {
+    xsltgeneric::generic_number_min_integer(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_sd_cooked,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xslt_number_lang_invalid() {
+    xsltgeneric::generic_number_lang_invalid(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_sd_cooked,
+    )
+    .expect("test failed")
+}
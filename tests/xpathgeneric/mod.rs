@@ -997,6 +997,117 @@ where
     Ok(())
 }
 
+pub fn generic_fncall_matches<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result(
+        "(matches('abracadabra', 'bra'), matches('abracadabra', '^a.*a$'), matches('Abracadabra', '^a.*a$'), matches('Abracadabra', '^a.*a$', 'i'))",
+    )?;
+    assert_eq!(s.len(), 4);
+    assert_eq!(
+        s.iter().map(|i| i.to_bool()).collect::<Vec<bool>>(),
+        vec![true, true, false, true]
+    );
+    Ok(())
+}
+pub fn generic_fncall_matches_flags<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result(
+        "(matches('a\nb', 'a.b'), matches('a\nb', 'a.b', 's'), matches('a\nb', '^b$', 'm'), matches('abc', 'a b c', 'x'), matches('a.c', 'a.c', 'q'), matches('abc', 'a.c', 'q'), matches('e', '[a-z-[aeiou]]'))",
+    )?;
+    assert_eq!(
+        s.iter().map(|i| i.to_bool()).collect::<Vec<bool>>(),
+        vec![false, true, true, true, true, false, false]
+    );
+    Ok(())
+}
+pub fn generic_fncall_matches_escapes<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result(
+        "(matches('a\u{a0}b', '\\s'), matches('a\u{a0}b', '^\\w\\S\\w$'), matches('a_b', '^\\w+$'), matches('\u{663}', '^\\d$'), matches('a&b~', '^[ab&&~~]+$'), matches('-', '^[-a]$'), matches('\u{e9}', '\\p{IsLatin-1Supplement}'), matches('a', '\\P{IsBasicLatin}'), matches('\u{3a9}', '^[\\p{IsGreek}a]$'))",
+    )?;
+    assert_eq!(
+        s.iter().map(|i| i.to_bool()).collect::<Vec<bool>>(),
+        vec![false, true, false, true, true, true, true, false, true]
+    );
+    let e = no_src_no_result::<N>("matches('a', '\\p{IsNoSuchBlock}')")
+        .expect_err("block should be unknown");
+    assert_eq!(
+        e.code.map(|c| c.to_string()),
+        Some(String::from("FORX0002"))
+    );
+    Ok(())
+}
+pub fn generic_fncall_matches_bad_flag<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let e = no_src_no_result::<N>("matches('abc', 'b', 'z')").expect_err("flag should be invalid");
+    assert_eq!(
+        e.code.map(|c| c.to_string()),
+        Some(String::from("FORX0001"))
+    );
+    Ok(())
+}
+pub fn generic_fncall_replace<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result(
+        "(replace('abracadabra', 'bra', '*'), replace('abracadabra', 'a(.)', 'a$1$1'), replace('darted', '^(.*?)d(.*)$', '$1c$2'), replace('a.b', '.', '\\$', 'q'))",
+    )?;
+    assert_eq!(
+        s.iter().map(|i| i.to_string()).collect::<Vec<String>>(),
+        vec!["a*cada*", "abbraccaddabbra", "carted", "a\\$b"]
+    );
+    Ok(())
+}
+pub fn generic_fncall_replace_zero_length<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let e = no_src_no_result::<N>("replace('abc', 'x*', '-')")
+        .expect_err("should not match zero-length string");
+    assert_eq!(
+        e.code.map(|c| c.to_string()),
+        Some(String::from("FORX0003"))
+    );
+    Ok(())
+}
+pub fn generic_fncall_tokenize<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result("tokenize('1, 15, 24, 50', ',\\s*')")?;
+    assert_eq!(
+        s.iter().map(|i| i.to_string()).collect::<Vec<String>>(),
+        vec!["1", "15", "24", "50"]
+    );
+    let t: Sequence<N> = no_src_no_result("tokenize(' red  green blue ')")?;
+    assert_eq!(
+        t.iter().map(|i| i.to_string()).collect::<Vec<String>>(),
+        vec!["red", "green", "blue"]
+    );
+    let u: Sequence<N> = no_src_no_result("tokenize('a\u{a0}b\tc')")?;
+    assert_eq!(
+        u.iter().map(|i| i.to_string()).collect::<Vec<String>>(),
+        vec!["a\u{a0}b", "c"]
+    );
+    Ok(())
+}
+
 pub fn generic_fncall_boolean_true<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
 where
     G: Fn() -> N,
@@ -921,6 +921,125 @@ where
     Ok(())
 }
 
+pub fn generic_number_multiple<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Vec<HashMap<String, String>>), Error>,
+{
+    let result = test_rig(
+        "<doc><item>a</item><item>b<item>c</item><item>d</item></item></doc>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform'>
+  <xsl:template match='child::doc'><xsl:apply-templates select='child::item'/></xsl:template>
+  <xsl:template match='child::item'><n><xsl:number level='multiple' format='1.a'/></n><xsl:apply-templates select='child::item'/></xsl:template>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    )?;
+    assert_eq!(result.to_xml(), "<n>1</n><n>2</n><n>2.a</n><n>2.b</n>");
+    Ok(())
+}
+
+pub fn generic_number_any<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Vec<HashMap<String, String>>), Error>,
+{
+    let result = test_rig(
+        "<doc><chap><fig/><fig/></chap><chap><fig/></chap></doc>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform'>
+  <xsl:template match='child::doc'><xsl:for-each select='descendant::fig'><xsl:number level='any'/>,</xsl:for-each>|<xsl:for-each select='descendant::fig'><xsl:number level='any' from='child::chap' start-at='0'/>,</xsl:for-each></xsl:template>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    )?;
+    assert_eq!(result.to_string(), "1,2,3,|0,1,0,");
+    Ok(())
+}
+
+pub fn generic_number_formatting<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Vec<HashMap<String, String>>), Error>,
+{
+    let result = test_rig(
+        "<doc/>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform'>
+  <xsl:template match='child::doc'><xsl:number value='1234567' grouping-separator=',' grouping-size='3'/>|<xsl:number value='22' ordinal='yes'/>|<xsl:number value='3' format='Ww' ordinal='yes'/>|<xsl:number value='28' format='(A)'/></xsl:template>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    )?;
+    assert_eq!(result.to_string(), "1,234,567|22nd|Third|(AB)");
+    Ok(())
+}
+
+pub fn generic_analyze_string<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Vec<HashMap<String, String>>), Error>,
+{
+    let result = test_rig(
+        "<doc>Tel: 555-1234, 555-9876.</doc>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform'>
+  <xsl:template match='child::doc'><xsl:analyze-string select='.' regex='(\d+)-(\d+)'><xsl:matching-substring><tel><xsl:value-of select='regex-group(2)'/>/<xsl:value-of select='regex-group(1)'/></tel></xsl:matching-substring><xsl:non-matching-substring><xsl:value-of select='position()'/>:<xsl:value-of select='.'/></xsl:non-matching-substring></xsl:analyze-string></xsl:template>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    )?;
+    assert_eq!(
+        result.to_xml(),
+        "1:Tel: <tel>1234/555</tel>3:, <tel>9876/555</tel>5:."
+    );
+    Ok(())
+}
+
+pub fn generic_analyze_string_flags<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Vec<HashMap<String, String>>), Error>,
+{
+    let result = test_rig(
+        "<doc>Alpha beta GAMMA</doc>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform'>
+  <xsl:template match='child::doc'><xsl:analyze-string select='.' regex='a' flags='i'><xsl:matching-substring>[<xsl:value-of select='.'/>]</xsl:matching-substring></xsl:analyze-string></xsl:template>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    )?;
+    assert_eq!(result.to_string(), "[A][a][a][A][A]");
+    Ok(())
+}
+
 pub fn attr_set_1<N: Node, G, H, J>(
     parse_from_str: G,
     parse_from_str_with_ns: J,
@@ -1004,3 +1123,203 @@ where
     );
     Ok(())
 }
+
+pub fn generic_group_starting_with<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Vec<HashMap<String, String>>), Error>,
+{
+    let result = test_rig(
+        "<doc><h1>One</h1><p>a</p><p>b</p><h1>Two</h1><p>c</p></doc>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform'>
+  <xsl:template match='child::doc'><xsl:for-each-group select='child::*' group-starting-with='child::h1'><section><xsl:copy-of select='current-group()'/></section></xsl:for-each-group></xsl:template>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    )?;
+    assert_eq!(
+        result.to_xml(),
+        "<section><h1>One</h1><p>a</p><p>b</p></section><section><h1>Two</h1><p>c</p></section>"
+    );
+    Ok(())
+}
+
+pub fn generic_group_ending_with<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Vec<HashMap<String, String>>), Error>,
+{
+    let result = test_rig(
+        "<doc><w>a</w><w>b</w><br/><w>c</w><br/><w>d</w></doc>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform'>
+  <xsl:template match='child::doc'><xsl:for-each-group select='child::*' group-ending-with='child::br'><line><xsl:value-of select='count(current-group())'/>:<xsl:value-of select='.'/></line></xsl:for-each-group></xsl:template>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    )?;
+    assert_eq!(
+        result.to_xml(),
+        "<line>3:a</line><line>2:c</line><line>1:d</line>"
+    );
+    Ok(())
+}
+
+pub fn generic_group_starting_with_sort<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Vec<HashMap<String, String>>), Error>,
+{
+    let result = test_rig(
+        "<doc><h1>B</h1><p>a</p><h1>A</h1><p>b</p><p>c</p><p>d</p></doc>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform'>
+  <xsl:template match='child::doc'><xsl:for-each-group select='child::*' group-starting-with='child::h1'><xsl:sort select='concat(last(), .)'/><xsl:value-of select='.'/></xsl:for-each-group></xsl:template>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    )?;
+    assert_eq!(result.to_xml(), "AB");
+    Ok(())
+}
+
+pub fn generic_group_starting_with_sort_error<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Vec<HashMap<String, String>>), Error>,
+{
+    match test_rig(
+        "<doc><h1>B</h1><p>a</p><h1>A</h1></doc>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform'>
+  <xsl:template match='child::doc'><xsl:for-each-group select='child::*' group-starting-with='child::h1'><xsl:sort select='$undefined'/><xsl:value-of select='.'/></xsl:for-each-group></xsl:template>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    ) {
+        Ok(_) => panic!("evaluation succeeded when it should have failed"),
+        Err(_) => Ok(()),
+    }
+}
+
+pub fn generic_number_any_attribute<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Vec<HashMap<String, String>>), Error>,
+{
+    let result = test_rig(
+        "<doc><a x='1'/><a x='2'/><a x='3'/></doc>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform'>
+  <xsl:template match='child::doc'><xsl:for-each select='child::a/attribute::x'><xsl:number level='any' count='child::a'/></xsl:for-each></xsl:template>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    )?;
+    assert_eq!(result.to_xml(), "123");
+    Ok(())
+}
+
+pub fn generic_number_lang<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Vec<HashMap<String, String>>), Error>,
+{
+    let result = test_rig(
+        "<doc/>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform'>
+  <xsl:template match='/'><xsl:number value='21' format='w' lang='de'/>|<xsl:number value='1101' format='w' lang='de'/>|<xsl:number value='3' format='Ww' ordinal='yes' lang='de'/>|<xsl:number value='20' format='w' ordinal='yes' lang='de'/>|<xsl:number value='2000000' format='w' lang='de'/>|<xsl:number value='21' format='w' lang='fr'/></xsl:template>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    )?;
+    assert_eq!(
+        result.to_xml(),
+        "einundzwanzig|eintausendeinhunderteins|Dritte|zwanzigste|zwei millionen|twenty one"
+    );
+    Ok(())
+}
+
+pub fn generic_number_min_integer<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Vec<HashMap<String, String>>), Error>,
+{
+    let result = test_rig(
+        "<doc n='-9223372036854775808'/>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform'>
+  <xsl:template match='/'><xsl:number value='string(doc/@n)' format='1' ordinal='yes'/>|<xsl:number value='string(doc/@n)' format='w' lang='de'/></xsl:template>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    )?;
+    let r = result.to_xml();
+    let (ordinal, words) = r.split_once('|').expect("no separator");
+    assert_eq!(ordinal, "-9223372036854775808th");
+    assert!(words.starts_with("minus neun millionen"));
+    assert!(words.ends_with("siebenhundertfünfundsiebzigtausendachthundertacht"));
+    Ok(())
+}
+
+pub fn generic_number_lang_invalid<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Vec<HashMap<String, String>>), Error>,
+{
+    match test_rig(
+        "<doc/>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform'>
+  <xsl:template match='/'><xsl:number value='1' format='w' lang='not a language'/></xsl:template>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    ) {
+        Ok(_) => panic!("evaluation succeeded when it should have failed"),
+        Err(e) => assert_eq!(e.code.unwrap().to_string(), "XTDE0030"),
+    }
+    Ok(())
+}